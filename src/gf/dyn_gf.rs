@@ -0,0 +1,230 @@
+use std::cell::Cell;
+use std::convert::{From, Into, TryInto};
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+thread_local! {
+    // Per-thread modulus for [`DynGF`], the "VarMod" pattern. Thread-local
+    // rather than a single process-wide atomic so that concurrent test cases
+    // (e.g. `cargo test`'s default multi-threaded runner) each get their own
+    // modulus instead of racing on a shared one.
+    static DYN_MODULUS: Cell<u64> = const { Cell::new(1_000_000_007) };
+}
+
+/// Set the modulus used by all [`DynGF`] values on the current thread.
+///
+/// Intended to be called once at the start of a test case, before any
+/// `DynGF` values are constructed for that case. Each thread has its own
+/// modulus, so this is safe to call from concurrently-running test cases.
+pub fn set_modulus(m: u64) {
+    DYN_MODULUS.with(|modulus| modulus.set(m));
+}
+
+fn modulus() -> u64 {
+    DYN_MODULUS.with(|modulus| modulus.get())
+}
+
+/// Finite field whose prime is chosen at runtime rather than at compile time.
+///
+/// `GF<P>` needs `P` fixed via `promote!`, which doesn't work when the modulus
+/// is part of the input. `DynGF` stores the modulus in a thread-local cell
+/// instead, reading it on every operation, and otherwise behaves like `GF<P>`.
+///
+/// ```
+/// use competitive::prelude::*;
+///
+/// set_modulus(1_000_000_007);
+/// let t = DynGF::new(2);
+/// assert_eq!(t.pow(100).as_u64(), 976371285);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DynGF(pub u64);
+
+impl Display for DynGF {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl DynGF {
+    pub fn new<T: TryInto<i64>>(v: T) -> Self {
+        Self(v.try_into().ok().unwrap().rem_euclid(modulus() as i64) as u64)
+    }
+
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(self, mut r: u64) -> Self {
+        let mut k = self;
+        let mut ret = DynGF::new(1);
+        while r > 0 {
+            if r % 2 != 0 {
+                ret = ret * k;
+            }
+            r /= 2;
+            k = k * k;
+        }
+        ret
+    }
+
+    // This requires the modulus is prime
+    pub fn recip(self) -> Self {
+        self.pow(modulus() - 2)
+    }
+}
+
+impl<T: Into<DynGF>> Add<T> for DynGF {
+    type Output = Self;
+    fn add(self, rhs: T) -> Self::Output {
+        Self::new(self.0 + rhs.into().0)
+    }
+}
+
+impl<T: Into<DynGF>> AddAssign<T> for DynGF {
+    fn add_assign(&mut self, rhs: T) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Into<DynGF>> Sub<T> for DynGF {
+    type Output = Self;
+    fn sub(self, rhs: T) -> Self::Output {
+        Self::new(self.0 + modulus() - rhs.into().0)
+    }
+}
+
+impl<T: Into<DynGF>> SubAssign<T> for DynGF {
+    fn sub_assign(&mut self, rhs: T) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Into<DynGF>> Mul<T> for DynGF {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.0 * rhs.into().0)
+    }
+}
+
+impl<T: Into<DynGF>> MulAssign<T> for DynGF {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: Into<DynGF>> Div<T> for DynGF {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self::Output {
+        self * rhs.into().recip()
+    }
+}
+
+impl<T: Into<DynGF>> DivAssign<T> for DynGF {
+    fn div_assign(&mut self, rhs: T) {
+        *self = *self / rhs;
+    }
+}
+
+// FIXME: Currently, rustc does not allow partial orphan instance
+// Use trait when it will be stabilized
+macro_rules! def_ops {
+    ($ty:ty) => {
+        impl Add<DynGF> for $ty {
+            type Output = DynGF;
+            fn add(self, rhs: DynGF) -> Self::Output {
+                DynGF::new(self) + rhs
+            }
+        }
+        impl Sub<DynGF> for $ty {
+            type Output = DynGF;
+            fn sub(self, rhs: DynGF) -> Self::Output {
+                DynGF::new(self) - rhs
+            }
+        }
+        impl Mul<DynGF> for $ty {
+            type Output = DynGF;
+            fn mul(self, rhs: DynGF) -> Self::Output {
+                DynGF::new(self) * rhs
+            }
+        }
+        impl Div<DynGF> for $ty {
+            type Output = DynGF;
+            fn div(self, rhs: DynGF) -> Self::Output {
+                DynGF::new(self) / rhs
+            }
+        }
+    };
+}
+
+def_ops!(i8);
+def_ops!(i16);
+def_ops!(i32);
+def_ops!(i64);
+def_ops!(isize);
+
+def_ops!(u8);
+def_ops!(u16);
+def_ops!(u32);
+def_ops!(u64);
+def_ops!(usize);
+
+impl Neg for DynGF {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(0) - self
+    }
+}
+
+impl<T: TryInto<i64>> From<T> for DynGF {
+    fn from(v: T) -> Self {
+        Self::new(v)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{set_modulus, DynGF};
+
+    #[test]
+    fn test_dyn_gf() {
+        set_modulus(1_000_000_007);
+
+        // operator test
+        let _ = DynGF::new(0);
+        let x: DynGF = 0.into();
+        let x = -x;
+
+        let x = 1 + x;
+        let x = x + 1;
+        let mut x = x + x;
+        x += 1;
+
+        let x = 1 - x;
+        let x = x - 1;
+        let mut x = x - x;
+        x -= 1;
+
+        let x = 1 * x;
+        let x = x * 1;
+        let mut x = x * x;
+        x *= 1;
+
+        let x = 1 / x;
+        let x = x / 1;
+        let mut x = x / x;
+        x /= 1;
+
+        // basic tests
+        let x: DynGF = 12345678.into();
+        let y: DynGF = 87654321.into();
+        assert_eq!(y * x * x.recip(), y);
+
+        assert_eq!(DynGF::new(2).pow(50).0, (1u64 << 50) % 1_000_000_007);
+
+        // a second, differently-moduled test case
+        set_modulus(998244353);
+        assert_eq!(DynGF::new(2).pow(50).0, (1u64 << 50) % 998244353);
+        set_modulus(1_000_000_007);
+    }
+}
@@ -0,0 +1,101 @@
+use super::GF;
+use typenum::Unsigned;
+
+/// Precomputed factorials, inverse factorials and small-integer inverses over `GF<P>`
+///
+/// Building a `Factorials<P>` up to `n` takes `O(n)` and turns `fact`, `inv_fact`,
+/// `binom`, `perm` and `inv` into `O(1)` lookups, which is the common shape of the
+/// binomial-coefficient tables needed in counting problems.
+///
+/// ```
+/// use competitive::prelude::*;
+///
+/// let f = competitive::gf::Factorials::<promote!(1000000007)>::new(10);
+/// assert_eq!(f.fact(5).as_u64(), 120);
+/// assert_eq!(f.binom(5, 2).as_u64(), 10);
+/// ```
+pub struct Factorials<P> {
+    fact: Vec<GF<P>>,
+    inv_fact: Vec<GF<P>>,
+    inv: Vec<GF<P>>,
+}
+
+impl<P: Unsigned> Factorials<P> {
+    /// Build the tables for `0..=n`.
+    pub fn new(n: usize) -> Self {
+        let mut fact = Vec::with_capacity(n + 1);
+        fact.push(GF::<P>::new(1));
+        for i in 1..=n {
+            fact.push(fact[i - 1] * i as u64);
+        }
+
+        let mut inv_fact = vec![GF::<P>::new(0); n + 1];
+        inv_fact[n] = fact[n].recip();
+        for i in (1..=n).rev() {
+            inv_fact[i - 1] = inv_fact[i] * i as u64;
+        }
+
+        let inv = crate::prime::inverses::<P>(n);
+
+        Self {
+            fact,
+            inv_fact,
+            inv,
+        }
+    }
+
+    /// `n!`
+    pub fn fact(&self, n: usize) -> GF<P> {
+        self.fact[n]
+    }
+
+    /// `(n!)^{-1}`
+    pub fn inv_fact(&self, n: usize) -> GF<P> {
+        self.inv_fact[n]
+    }
+
+    /// `i^{-1}` for `1 <= i <= n`
+    pub fn inv(&self, i: usize) -> GF<P> {
+        self.inv[i]
+    }
+
+    /// `n! / (n-k)!`, or `0` if `k > n`
+    pub fn perm(&self, n: usize, k: usize) -> GF<P> {
+        if k > n {
+            return GF::<P>::new(0);
+        }
+        self.fact[n] * self.inv_fact[n - k]
+    }
+
+    /// `n! / (k! (n-k)!)`, or `0` if `k > n`
+    pub fn binom(&self, n: usize, k: usize) -> GF<P> {
+        if k > n {
+            return GF::<P>::new(0);
+        }
+        self.fact[n] * self.inv_fact[k] * self.inv_fact[n - k]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Factorials;
+    use typenum_promote::promote;
+
+    #[test]
+    fn test_factorials() {
+        type P = promote!(1000000007);
+
+        let f = Factorials::<P>::new(20);
+
+        assert_eq!(f.fact(0).as_u64(), 1);
+        assert_eq!(f.fact(5).as_u64(), 120);
+        assert_eq!(f.binom(5, 2).as_u64(), 10);
+        assert_eq!(f.binom(10, 0).as_u64(), 1);
+        assert_eq!(f.binom(10, 11).as_u64(), 0);
+        assert_eq!(f.perm(5, 2).as_u64(), 20);
+
+        for i in 1..=20u64 {
+            assert_eq!((f.inv(i as usize) * i).as_u64(), 1);
+        }
+    }
+}
@@ -4,6 +4,14 @@ use std::marker::PhantomData;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 use typenum::Unsigned;
 
+mod dyn_gf;
+mod fact;
+mod montgomery;
+pub mod ntt;
+pub use dyn_gf::{set_modulus, DynGF};
+pub use fact::Factorials;
+pub use montgomery::MontGF;
+
 /// Finite field of prime `P`
 ///
 /// `P` must be an instance of `typenum::Unsigned` and a prime number.
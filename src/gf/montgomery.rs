@@ -0,0 +1,300 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::convert::{From, Into, TryInto};
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use typenum::Unsigned;
+
+thread_local! {
+    // (n', R^2 mod P) keyed by P, so the Newton iteration and the 128-bit mod
+    // in their derivation are paid once per modulus rather than on every op.
+    static MONT_CONSTS: RefCell<HashMap<u64, (u64, u64)>> = RefCell::new(HashMap::new());
+}
+
+fn mont_consts(p: u64) -> (u64, u64) {
+    MONT_CONSTS.with(|cache| {
+        *cache.borrow_mut().entry(p).or_insert_with(|| {
+            let r_mod_p = (1u128 << 64) % p as u128;
+            let r2 = ((r_mod_p * r_mod_p) % p as u128) as u64;
+            (n_prime(p), r2)
+        })
+    })
+}
+
+/// Finite field of prime `P`, stored internally in Montgomery form.
+///
+/// `GF::new` pays a 64-bit division on every construction, and every `+`/`-`/`*`
+/// goes through `GF`'s own division-based reduction, which dominates hot loops
+/// such as NTT butterflies and matrix exponentiation. `MontGF<P>` instead keeps
+/// values as `a * R mod P` with `R = 2^64`, and reduces products with REDC
+/// (a handful of multiplications and shifts, no division) instead of `%`.
+///
+/// The public API mirrors `GF<P>` exactly -- conversion in and out of
+/// Montgomery form happens at the boundary (`new`/`as_u64`), so callers can
+/// swap one for the other without other changes.
+///
+/// ```
+/// use competitive::prelude::*;
+/// use competitive::gf::MontGF;
+///
+/// type M = MontGF<promote!(1000000007)>;
+///
+/// let t = M::new(2);
+/// assert_eq!(t.pow(100).as_u64(), 976371285);
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct MontGF<P>(u64, PhantomData<P>);
+
+impl<P> Clone for MontGF<P> {
+    fn clone(&self) -> Self {
+        Self(self.0, PhantomData::<P>)
+    }
+}
+
+impl<P> Copy for MontGF<P> {}
+
+impl<P: Unsigned> Display for MontGF<P> {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_u64())
+    }
+}
+
+// n' = -P^{-1} mod 2^64, found by Newton's iteration on the 2-adic inverse:
+// if x is the inverse of p mod 2^k, then x*(2 - p*x) is the inverse mod 2^(2k).
+fn n_prime(p: u64) -> u64 {
+    let mut x = p; // correct mod 2^3 for any odd p
+    for _ in 0..5 {
+        x = x.wrapping_mul(2u64.wrapping_sub(p.wrapping_mul(x)));
+    }
+    x.wrapping_neg()
+}
+
+// REDC(t) = (t + (t_low * n' mod 2^64) * P) / 2^64, then subtract P if needed.
+fn redc(t: u128, p: u64, np: u64) -> u64 {
+    let t_low = t as u64;
+    let m = t_low.wrapping_mul(np);
+    let t = (t + (m as u128) * (p as u128)) >> 64;
+    let t = t as u64;
+    if t >= p {
+        t - p
+    } else {
+        t
+    }
+}
+
+impl<P: Unsigned> MontGF<P> {
+    fn p() -> u64 {
+        P::to_u64()
+    }
+
+    fn to_mont(v: u64) -> u64 {
+        let (np, r2) = mont_consts(Self::p());
+        redc((v as u128) * (r2 as u128), Self::p(), np)
+    }
+
+    fn from_mont(v: u64) -> u64 {
+        let (np, _) = mont_consts(Self::p());
+        redc(v as u128, Self::p(), np)
+    }
+
+    pub fn new<T: TryInto<i64>>(v: T) -> Self {
+        let v = v.try_into().ok().unwrap().rem_euclid(Self::p() as i64) as u64;
+        Self(Self::to_mont(v), PhantomData::<P>)
+    }
+
+    pub fn as_u64(&self) -> u64 {
+        Self::from_mont(self.0)
+    }
+
+    pub fn pow(self, mut r: u64) -> Self {
+        let mut k = self;
+        let mut ret = MontGF::<P>::new(1);
+        while r > 0 {
+            if r % 2 != 0 {
+                ret = ret * k;
+            }
+            r /= 2;
+            k = k * k;
+        }
+        ret
+    }
+
+    // This requires P is prime
+    pub fn recip(self) -> Self {
+        self.pow(Self::p() - 2)
+    }
+}
+
+impl<P: Unsigned, T: Into<MontGF<P>>> Add<T> for MontGF<P> {
+    type Output = Self;
+    fn add(self, rhs: T) -> Self::Output {
+        let sum = self.0 + rhs.into().0;
+        let p = Self::p();
+        Self(if sum >= p { sum - p } else { sum }, PhantomData::<P>)
+    }
+}
+
+impl<P: Unsigned, T: Into<MontGF<P>>> AddAssign<T> for MontGF<P> {
+    fn add_assign(&mut self, rhs: T) {
+        *self = *self + rhs;
+    }
+}
+
+impl<P: Unsigned, T: Into<MontGF<P>>> Sub<T> for MontGF<P> {
+    type Output = Self;
+    fn sub(self, rhs: T) -> Self::Output {
+        let rhs = rhs.into().0;
+        let p = Self::p();
+        let diff = if self.0 >= rhs { self.0 - rhs } else { self.0 + p - rhs };
+        Self(diff, PhantomData::<P>)
+    }
+}
+
+impl<P: Unsigned, T: Into<MontGF<P>>> SubAssign<T> for MontGF<P> {
+    fn sub_assign(&mut self, rhs: T) {
+        *self = *self - rhs;
+    }
+}
+
+impl<P: Unsigned, T: Into<MontGF<P>>> Mul<T> for MontGF<P> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        let t = (self.0 as u128) * (rhs.into().0 as u128);
+        let (np, _) = mont_consts(Self::p());
+        Self(redc(t, Self::p(), np), PhantomData::<P>)
+    }
+}
+
+impl<P: Unsigned, T: Into<MontGF<P>>> MulAssign<T> for MontGF<P> {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<P: Unsigned, T: Into<MontGF<P>>> Div<T> for MontGF<P> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self::Output {
+        self * rhs.into().recip()
+    }
+}
+
+impl<P: Unsigned, T: Into<MontGF<P>>> DivAssign<T> for MontGF<P> {
+    fn div_assign(&mut self, rhs: T) {
+        *self = *self / rhs;
+    }
+}
+
+macro_rules! def_ops {
+    ($ty:ty) => {
+        impl<P: Unsigned> Add<MontGF<P>> for $ty {
+            type Output = MontGF<P>;
+            fn add(self, rhs: MontGF<P>) -> Self::Output {
+                MontGF::<P>::new(self) + rhs
+            }
+        }
+        impl<P: Unsigned> Sub<MontGF<P>> for $ty {
+            type Output = MontGF<P>;
+            fn sub(self, rhs: MontGF<P>) -> Self::Output {
+                MontGF::<P>::new(self) - rhs
+            }
+        }
+        impl<P: Unsigned> Mul<MontGF<P>> for $ty {
+            type Output = MontGF<P>;
+            fn mul(self, rhs: MontGF<P>) -> Self::Output {
+                MontGF::<P>::new(self) * rhs
+            }
+        }
+        impl<P: Unsigned> Div<MontGF<P>> for $ty {
+            type Output = MontGF<P>;
+            fn div(self, rhs: MontGF<P>) -> Self::Output {
+                MontGF::<P>::new(self) / rhs
+            }
+        }
+    };
+}
+
+def_ops!(i8);
+def_ops!(i16);
+def_ops!(i32);
+def_ops!(i64);
+def_ops!(isize);
+
+def_ops!(u8);
+def_ops!(u16);
+def_ops!(u32);
+def_ops!(u64);
+def_ops!(usize);
+
+impl<P: Unsigned> Neg for MontGF<P> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(0) - self
+    }
+}
+
+impl<P: Unsigned, T: TryInto<i64>> From<T> for MontGF<P> {
+    fn from(v: T) -> Self {
+        Self::new(v)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MontGF;
+    use typenum_promote::promote;
+
+    #[test]
+    fn test_mont_gf() {
+        type P = promote!(1000000007);
+        type M = MontGF<P>;
+
+        let x: M = 0.into();
+        let x = -x;
+
+        let x = 1 + x;
+        let x = x + 1;
+        let mut x = x + x;
+        x += 1;
+
+        let x = 1 - x;
+        let x = x - 1;
+        let mut x = x - x;
+        x -= 1;
+
+        let x = 1 * x;
+        let x = x * 1;
+        let mut x = x * x;
+        x *= 1;
+
+        let x = 1 / x;
+        let x = x / 1;
+        let mut x = x / x;
+        x /= 1;
+
+        let x: M = 12345678.into();
+        let y: M = 87654321.into();
+        assert_eq!(y * x * x.recip(), y);
+
+        assert_eq!(M::new(2).pow(50).as_u64(), (1u64 << 50) % 1_000_000_007);
+    }
+
+    #[test]
+    fn test_matches_plain_gf() {
+        type P = promote!(998244353);
+        type G = crate::gf::GF<P>;
+        type M = MontGF<P>;
+
+        for a in [0u64, 1, 2, 998244352, 123456789] {
+            for b in [0u64, 1, 5, 998244352, 314159265] {
+                let ga = G::new(a);
+                let gb = G::new(b);
+                let ma = M::new(a);
+                let mb = M::new(b);
+                assert_eq!((ga + gb).as_u64(), (ma + mb).as_u64());
+                assert_eq!((ga - gb).as_u64(), (ma - mb).as_u64());
+                assert_eq!((ga * gb).as_u64(), (ma * mb).as_u64());
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use super::convolution;
+use crate::gf::GF;
+use typenum::Unsigned;
+use typenum_promote::promote;
+
+// Three NTT-friendly primes, each `c * 2^k + 1` with large enough `k` to cover
+// any convolution length seen in practice, chosen so that p1*p2*p3 comfortably
+// exceeds `n * (m-1)^2` for any `m` up to 1e9 and any reasonable `n`.
+type P1 = promote!(167772161);
+type P2 = promote!(469762049);
+type P3 = promote!(754974721);
+
+crate::impl_ntt_prime!(167772161, 3, 25);
+crate::impl_ntt_prime!(754974721, 11, 24);
+
+/// Multiply two polynomials whose coefficients are reduced modulo an
+/// arbitrary `m` (not necessarily NTT-friendly), in `O(n log n)`.
+///
+/// Runs the convolution independently under three fixed NTT primes whose
+/// product exceeds the largest possible true coefficient, then reconstructs
+/// each coefficient with Garner's algorithm before reducing mod `m`.
+///
+/// ```
+/// use competitive::prelude::*;
+///
+/// type GF = competitive::gf::GF<promote!(1000000007)>;
+///
+/// let a: Vec<GF> = vec![1, 2, 3].into_iter().map(GF::new).collect();
+/// let b: Vec<GF> = vec![1, 2, 3].into_iter().map(GF::new).collect();
+/// let c = convolution_arbitrary(&a, &b);
+/// assert_eq!(c.iter().map(|x| x.as_u64()).collect::<Vec<_>>(), vec![1, 4, 10, 12, 9]);
+/// ```
+pub fn convolution_arbitrary<M: Unsigned>(a: &[GF<M>], b: &[GF<M>]) -> Vec<GF<M>> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+
+    let a1: Vec<GF<P1>> = a.iter().map(|x| GF::<P1>::new(x.as_u64())).collect();
+    let b1: Vec<GF<P1>> = b.iter().map(|x| GF::<P1>::new(x.as_u64())).collect();
+    let a2: Vec<GF<P2>> = a.iter().map(|x| GF::<P2>::new(x.as_u64())).collect();
+    let b2: Vec<GF<P2>> = b.iter().map(|x| GF::<P2>::new(x.as_u64())).collect();
+    let a3: Vec<GF<P3>> = a.iter().map(|x| GF::<P3>::new(x.as_u64())).collect();
+    let b3: Vec<GF<P3>> = b.iter().map(|x| GF::<P3>::new(x.as_u64())).collect();
+
+    let c1 = convolution(&a1, &b1);
+    let c2 = convolution(&a2, &b2);
+    let c3 = convolution(&a3, &b3);
+
+    let p1 = P1::to_u64();
+    let p2 = P2::to_u64();
+    let p3 = P3::to_u64();
+
+    // inverses needed by Garner's recurrence, computed once up front
+    let inv_p1_p2 = GF::<P2>::new(p1).recip().as_u64();
+    let inv_p1p2_p3 = GF::<P3>::new(p1 % p3 * (p2 % p3) % p3).recip().as_u64();
+
+    let m = M::to_u64();
+    c1.iter()
+        .zip(c2.iter())
+        .zip(c3.iter())
+        .map(|((&r1, &r2), &r3)| {
+            garner(
+                r1.as_u64(),
+                r2.as_u64(),
+                r3.as_u64(),
+                p1,
+                p2,
+                p3,
+                inv_p1_p2,
+                inv_p1p2_p3,
+                m,
+            )
+        })
+        .map(GF::<M>::new)
+        .collect()
+}
+
+// Garner's algorithm: reconstruct x mod (p1*p2*p3) from its residues mod each
+// prime, reducing the running result mod m as we go since x itself may not
+// fit, but m always does.
+#[allow(clippy::too_many_arguments)]
+fn garner(
+    r1: u64,
+    r2: u64,
+    r3: u64,
+    p1: u64,
+    p2: u64,
+    p3: u64,
+    inv_p1_p2: u64,
+    inv_p1p2_p3: u64,
+    m: u64,
+) -> u64 {
+    let x1 = r1;
+    let x2 = (r2 + p2 - x1 % p2) % p2 * inv_p1_p2 % p2;
+    let x3 = (r3 + p3 - x1 % p3 + p3 - x2 * (p1 % p3) % p3) % p3 * inv_p1p2_p3 % p3;
+
+    let mut x = x1 % m;
+    x = (x + x2 % m * (p1 % m) % m) % m;
+    let t = x3 % m * (p1 % m) % m;
+    x = (x + t * (p2 % m) % m) % m;
+    x
+}
+
+#[cfg(test)]
+mod test {
+    use super::convolution_arbitrary;
+    use crate::gf::GF;
+    use typenum_promote::promote;
+
+    #[test]
+    fn test_convolution_arbitrary() {
+        type M = promote!(1000000007);
+
+        let a: Vec<GF<M>> = vec![1, 2, 3].into_iter().map(GF::new).collect();
+        let b: Vec<GF<M>> = vec![1, 2, 3].into_iter().map(GF::new).collect();
+        let c = convolution_arbitrary(&a, &b);
+        let expected = vec![1u64, 4, 10, 12, 9];
+        assert_eq!(c.iter().map(|x| x.as_u64()).collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_convolution_arbitrary_large_coeffs() {
+        type M = promote!(1000000007);
+
+        let a: Vec<GF<M>> = vec![1_000_000_006, 999_999_999]
+            .into_iter()
+            .map(GF::new)
+            .collect();
+        let b: Vec<GF<M>> = vec![1_000_000_006, 999_999_999]
+            .into_iter()
+            .map(GF::new)
+            .collect();
+        let c = convolution_arbitrary(&a, &b);
+
+        assert_eq!(
+            c[0].as_u64(),
+            (1_000_000_006u128 * 1_000_000_006u128 % 1_000_000_007) as u64
+        );
+        assert_eq!(
+            c[2].as_u64(),
+            (999_999_999u128 * 999_999_999u128 % 1_000_000_007) as u64
+        );
+    }
+
+    // Regression test for an overflow in garner()'s final combination step:
+    // reducing mod m only at the end of a three-term product overflows u64
+    // for realistic m, which a small hand-picked x3 (as in
+    // test_convolution_arbitrary_large_coeffs) doesn't exercise. Compare
+    // against a plain O(n^2) u128 reference over many sizeable coefficients
+    // so a large x3 is exercised somewhere in the sweep.
+    #[test]
+    fn test_convolution_arbitrary_matches_reference() {
+        type M = promote!(1000000007);
+        let m: u128 = 1_000_000_007;
+
+        let n = 40;
+        let coeffs: Vec<u64> = (0..n)
+            .map(|i: u64| (i * i * 1_000_003 + 999_999_937) % 1_000_000_007)
+            .collect();
+
+        let a: Vec<GF<M>> = coeffs.iter().map(|&x| GF::new(x)).collect();
+        let b: Vec<GF<M>> = coeffs.iter().rev().map(|&x| GF::new(x)).collect();
+
+        let mut reference = vec![0u128; 2 * n as usize - 1];
+        for (i, &x) in coeffs.iter().enumerate() {
+            for (j, &y) in coeffs.iter().rev().enumerate() {
+                reference[i + j] = (reference[i + j] + x as u128 * y as u128) % m;
+            }
+        }
+
+        let c = convolution_arbitrary(&a, &b);
+        assert_eq!(
+            c.iter().map(|x| x.as_u64() as u128).collect::<Vec<_>>(),
+            reference
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use super::GF;
+use typenum::Unsigned;
+use typenum_promote::promote;
+
+mod arbitrary;
+pub use arbitrary::convolution_arbitrary;
+
+/// An NTT-friendly prime, i.e. one of the form `c * 2^k + 1` with a known
+/// primitive root, so that a length-`n` number-theoretic transform exists
+/// for every power of two `n <= 2^k`.
+pub trait NttPrime: Unsigned {
+    /// A primitive root of the multiplicative group mod `Self`.
+    const PRIMITIVE_ROOT: u64;
+
+    /// `k`, the largest power of two dividing `Self - 1`: the longest
+    /// transform length this prime supports is `2^MAX_LOG_LEN`.
+    const MAX_LOG_LEN: u32;
+}
+
+/// Registers a `typenum_promote`-generated modulus type as NTT-friendly.
+///
+/// ```ignore
+/// impl_ntt_prime!(469762049, 3, 26);
+/// ```
+#[macro_export]
+macro_rules! impl_ntt_prime {
+    ($p:expr, $g:expr, $max_log_len:expr) => {
+        impl $crate::gf::ntt::NttPrime for typenum_promote::promote!($p) {
+            const PRIMITIVE_ROOT: u64 = $g;
+            const MAX_LOG_LEN: u32 = $max_log_len;
+        }
+    };
+}
+
+impl NttPrime for promote!(998244353) {
+    const PRIMITIVE_ROOT: u64 = 3;
+    const MAX_LOG_LEN: u32 = 23;
+}
+
+impl NttPrime for promote!(469762049) {
+    const PRIMITIVE_ROOT: u64 = 3;
+    const MAX_LOG_LEN: u32 = 26;
+}
+
+/// In-place iterative NTT (Cooley-Tukey, decimation in time).
+///
+/// `a.len()` must be a power of two. `invert` selects the inverse transform,
+/// which the caller still needs to scale by `n^{-1}`.
+fn transform<P: NttPrime>(a: &mut [GF<P>], invert: bool) {
+    let n = a.len();
+    debug_assert!(n.is_power_of_two());
+    assert!(
+        n <= 1usize << P::MAX_LOG_LEN,
+        "transform length {} exceeds the maximum length 2^{} supported by this NTT prime",
+        n,
+        P::MAX_LOG_LEN,
+    );
+
+    // bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j ^= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let mut w = GF::<P>::new(P::PRIMITIVE_ROOT).pow((P::to_u64() - 1) / len as u64);
+        if invert {
+            w = w.recip();
+        }
+        let mut i = 0;
+        while i < n {
+            let mut wn = GF::<P>::new(1);
+            for k in 0..len / 2 {
+                let u = a[i + k];
+                let v = a[i + k + len / 2] * wn;
+                a[i + k] = u + v;
+                a[i + k + len / 2] = u - v;
+                wn = wn * w;
+            }
+            i += len;
+        }
+        len <<= 1;
+    }
+
+    if invert {
+        let n_inv = GF::<P>::new(n as u64).recip();
+        for x in a.iter_mut() {
+            *x = *x * n_inv;
+        }
+    }
+}
+
+/// Multiply two polynomials over `GF<P>` in `O(n log n)` via NTT.
+///
+/// `P` must be an NTT-friendly prime (see [`NttPrime`]), such as `998244353`
+/// or `469762049`.
+///
+/// ```
+/// use competitive::prelude::*;
+/// use competitive::gf::ntt::convolution;
+///
+/// type GF = competitive::gf::GF<promote!(998244353)>;
+///
+/// let a = vec![GF::new(1), GF::new(2), GF::new(3)];
+/// let b = vec![GF::new(1), GF::new(2), GF::new(3)];
+/// let c = convolution(&a, &b);
+/// assert_eq!(c.iter().map(|x| x.as_u64()).collect::<Vec<_>>(), vec![1, 4, 10, 12, 9]);
+/// ```
+pub fn convolution<P: NttPrime>(a: &[GF<P>], b: &[GF<P>]) -> Vec<GF<P>> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+
+    let result_len = a.len() + b.len() - 1;
+    let n = result_len.next_power_of_two();
+
+    let mut fa = a.to_vec();
+    fa.resize(n, GF::<P>::new(0));
+    let mut fb = b.to_vec();
+    fb.resize(n, GF::<P>::new(0));
+
+    transform(&mut fa, false);
+    transform(&mut fb, false);
+
+    for i in 0..n {
+        fa[i] = fa[i] * fb[i];
+    }
+
+    transform(&mut fa, true);
+    fa.truncate(result_len);
+    fa
+}
+
+#[cfg(test)]
+mod test {
+    use super::convolution;
+    use typenum_promote::promote;
+
+    #[test]
+    fn test_convolution() {
+        type GF = crate::gf::GF<promote!(998244353)>;
+
+        let a: Vec<GF> = vec![1, 2, 3].into_iter().map(GF::new).collect();
+        let b: Vec<GF> = vec![1, 2, 3].into_iter().map(GF::new).collect();
+        let c = convolution(&a, &b);
+        let expected = vec![1, 4, 10, 12, 9];
+        assert_eq!(
+            c.iter().map(|x| x.as_u64()).collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_convolution_empty() {
+        type GF = crate::gf::GF<promote!(998244353)>;
+
+        let a: Vec<GF> = vec![];
+        let b: Vec<GF> = vec![GF::new(1)];
+        assert!(convolution(&a, &b).is_empty());
+    }
+}
@@ -0,0 +1,187 @@
+use crate::gf::GF;
+use std::ops::{Add, Mul};
+use typenum::Unsigned;
+
+/// A square matrix over `GF<P>` supporting `+`, `*` and fast exponentiation.
+///
+/// This fills the gap left by `ndarray`, which has no notion of a modulus:
+/// linear-recurrence and random-walk problems that need `m.pow(t)` under a
+/// modulus can use this instead.
+///
+/// ```
+/// use competitive::prelude::*;
+/// use competitive::matrix::Matrix;
+///
+/// type GF = competitive::gf::GF<promote!(1000000007)>;
+///
+/// // Fibonacci via matrix exponentiation
+/// let m = Matrix::from_vec(vec![
+///     vec![GF::new(1), GF::new(1)],
+///     vec![GF::new(1), GF::new(0)],
+/// ]);
+/// let f = m.pow(10).mul_vec(&[GF::new(1), GF::new(0)]);
+/// assert_eq!(f[1].as_u64(), 55);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix<P> {
+    rows: usize,
+    cols: usize,
+    data: Vec<GF<P>>,
+}
+
+impl<P: Unsigned> Matrix<P> {
+    /// Build a matrix from a rectangular `Vec<Vec<GF<P>>>`.
+    pub fn from_vec(v: Vec<Vec<GF<P>>>) -> Self {
+        let rows = v.len();
+        let cols = if rows == 0 { 0 } else { v[0].len() };
+        let data = v.into_iter().flatten().collect();
+        Self { rows, cols, data }
+    }
+
+    /// A `n x n` zero matrix.
+    pub fn zero(n: usize) -> Self {
+        Self {
+            rows: n,
+            cols: n,
+            data: vec![GF::<P>::new(0); n * n],
+        }
+    }
+
+    /// The `n x n` identity matrix.
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zero(n);
+        for i in 0..n {
+            m[(i, i)] = GF::<P>::new(1);
+        }
+        m
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Matrix-vector product.
+    pub fn mul_vec(&self, v: &[GF<P>]) -> Vec<GF<P>> {
+        assert_eq!(self.cols, v.len());
+        (0..self.rows)
+            .map(|i| {
+                (0..self.cols)
+                    .fold(GF::<P>::new(0), |acc, j| acc + self[(i, j)] * v[j])
+            })
+            .collect()
+    }
+
+    /// Raise a square matrix to the `exp`-th power by binary exponentiation,
+    /// `O(k^3 log exp)` for a `k x k` matrix.
+    pub fn pow(&self, mut exp: u64) -> Self {
+        assert_eq!(self.rows, self.cols, "pow requires a square matrix");
+        let mut base = self.clone();
+        let mut ret = Self::identity(self.rows);
+        while exp > 0 {
+            if exp % 2 != 0 {
+                ret = &ret * &base;
+            }
+            base = &base * &base;
+            exp /= 2;
+        }
+        ret
+    }
+}
+
+impl<P: Unsigned> std::ops::Index<(usize, usize)> for Matrix<P> {
+    type Output = GF<P>;
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<P: Unsigned> std::ops::IndexMut<(usize, usize)> for Matrix<P> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+impl<P: Unsigned> Add for &Matrix<P> {
+    type Output = Matrix<P>;
+    fn add(self, rhs: Self) -> Matrix<P> {
+        assert_eq!((self.rows, self.cols), (rhs.rows, rhs.cols));
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self
+                .data
+                .iter()
+                .zip(rhs.data.iter())
+                .map(|(&a, &b)| a + b)
+                .collect(),
+        }
+    }
+}
+
+impl<P: Unsigned> Mul for &Matrix<P> {
+    type Output = Matrix<P>;
+    fn mul(self, rhs: Self) -> Matrix<P> {
+        assert_eq!(self.cols, rhs.rows);
+        let mut ret = Matrix {
+            rows: self.rows,
+            cols: rhs.cols,
+            data: vec![GF::<P>::new(0); self.rows * rhs.cols],
+        };
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(i, k)];
+                if a.as_u64() == 0 {
+                    continue;
+                }
+                for j in 0..rhs.cols {
+                    ret[(i, j)] = ret[(i, j)] + a * rhs[(k, j)];
+                }
+            }
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Matrix;
+    use crate::gf::GF;
+    use typenum_promote::promote;
+
+    type P = promote!(1000000007);
+
+    #[test]
+    fn test_identity_and_add() {
+        let id = Matrix::<P>::identity(2);
+        let sum = &id + &id;
+        assert_eq!(sum[(0, 0)].as_u64(), 2);
+        assert_eq!(sum[(0, 1)].as_u64(), 0);
+    }
+
+    #[test]
+    fn test_mul() {
+        let a = Matrix::<P>::from_vec(vec![
+            vec![GF::new(1), GF::new(2)],
+            vec![GF::new(3), GF::new(4)],
+        ]);
+        let b = Matrix::<P>::identity(2);
+        let c = &a * &b;
+        assert_eq!(c, a);
+    }
+
+    #[test]
+    fn test_fibonacci_pow() {
+        let m = Matrix::<P>::from_vec(vec![
+            vec![GF::new(1), GF::new(1)],
+            vec![GF::new(1), GF::new(0)],
+        ]);
+        let f = m.pow(10).mul_vec(&[GF::new(1), GF::new(0)]);
+        // F(11) = 89, F(10) = 55
+        assert_eq!(f[0].as_u64(), 89);
+        assert_eq!(f[1].as_u64(), 55);
+    }
+}
@@ -26,9 +26,11 @@ pub use comprehension::*;
 pub use crate::binary_search::{binary_search, lower_bound, upper_bound};
 pub use crate::bits::{power_bitset, SmallBitSet};
 pub use crate::display::{AtCoder, Mat, Vertical};
-pub use crate::gf::GF;
+pub use crate::gf::ntt::{convolution, convolution_arbitrary};
+pub use crate::gf::{set_modulus, DynGF, Factorials, MontGF, GF};
 pub use crate::inf::{MaybeInf, MaybeInf::*};
 pub use crate::ix::{Board, Ix2};
+pub use crate::matrix::Matrix;
 pub use crate::monoid::{Max, Min, Monoid, Product, Sum};
 pub use crate::prime::*;
 pub use crate::segment_tree::SegmentTree;
@@ -0,0 +1,152 @@
+use crate::gf::GF;
+use typenum::Unsigned;
+
+/// Smallest-prime-factor sieve, built in `O(n)` by the standard linear sieve.
+///
+/// Once built, `factorize` runs in `O(log x)` by repeatedly dividing out the
+/// stored smallest prime factor instead of trial-dividing, which is the
+/// common shape needed when many queried values must be factorized.
+///
+/// ```
+/// use competitive::prelude::*;
+///
+/// let sieve = LinearSieve::new(100);
+/// assert!(sieve.is_prime(97));
+/// assert!(!sieve.is_prime(96));
+/// assert_eq!(sieve.factorize(84), vec![(2, 2), (3, 1), (7, 1)]);
+/// ```
+pub struct LinearSieve {
+    spf: Vec<u32>,
+    primes: Vec<u32>,
+}
+
+impl LinearSieve {
+    /// Sieve all smallest prime factors up to and including `n`.
+    pub fn new(n: usize) -> Self {
+        let mut spf = vec![0u32; n + 1];
+        let mut primes = Vec::new();
+
+        for i in 2..=n {
+            if spf[i] == 0 {
+                spf[i] = i as u32;
+                primes.push(i as u32);
+            }
+            for &p in &primes {
+                if p > spf[i] || (i as u64) * (p as u64) > n as u64 {
+                    break;
+                }
+                spf[i * p as usize] = p;
+            }
+        }
+
+        Self { spf, primes }
+    }
+
+    /// Is `x` prime? `x` must be `<= n`.
+    pub fn is_prime(&self, x: u64) -> bool {
+        x >= 2 && self.spf[x as usize] == x as u32
+    }
+
+    /// All primes `<= n`, in increasing order.
+    pub fn primes(&self) -> &[u32] {
+        &self.primes
+    }
+
+    /// Prime factorization of `x` as `(prime, exponent)` pairs in increasing
+    /// order of prime, in `O(log x)`. `x` must be `<= n` and `>= 1`.
+    pub fn factorize(&self, mut x: u64) -> Vec<(u64, u32)> {
+        let mut factors = Vec::new();
+        while x > 1 {
+            let p = self.spf[x as usize] as u64;
+            let mut e = 0;
+            while x % p == 0 {
+                x /= p;
+                e += 1;
+            }
+            factors.push((p, e));
+        }
+        factors
+    }
+
+    /// All divisors of `x` in increasing order. `x` must be `<= n` and `>= 1`.
+    pub fn divisors(&self, x: u64) -> Vec<u64> {
+        let mut divisors = vec![1u64];
+        for (p, e) in self.factorize(x) {
+            let mut next = Vec::with_capacity(divisors.len() * (e as usize + 1));
+            let mut pk = 1u64;
+            for _ in 0..=e {
+                for &d in &divisors {
+                    next.push(d * pk);
+                }
+                pk *= p;
+            }
+            divisors = next;
+        }
+        divisors.sort_unstable();
+        divisors
+    }
+}
+
+/// Precompute `i^{-1} mod P` for `1 <= i <= n` in `O(n)`, via the linear
+/// recurrence `inv[i] = -(P/i) * inv[P % i]`.
+///
+/// ```
+/// use competitive::prelude::*;
+///
+/// type GF = competitive::gf::GF<promote!(1000000007)>;
+///
+/// let inv = inverses::<promote!(1000000007)>(10);
+/// assert_eq!((inv[7] * 7u64).as_u64(), 1);
+/// ```
+pub fn inverses<P: Unsigned>(n: usize) -> Vec<GF<P>> {
+    let p = P::to_u64();
+    let mut inv = vec![GF::<P>::new(0); n + 1];
+    if n >= 1 {
+        inv[1] = GF::<P>::new(1);
+    }
+    for i in 2..=n {
+        let i64_ = i as u64;
+        inv[i] = -GF::<P>::new(p / i64_) * inv[(p % i64_) as usize];
+    }
+    inv
+}
+
+#[cfg(test)]
+mod test {
+    use super::{inverses, LinearSieve};
+    use typenum_promote::promote;
+
+    #[test]
+    fn test_is_prime() {
+        let sieve = LinearSieve::new(100);
+        assert!(sieve.is_prime(2));
+        assert!(sieve.is_prime(97));
+        assert!(!sieve.is_prime(1));
+        assert!(!sieve.is_prime(96));
+    }
+
+    #[test]
+    fn test_factorize() {
+        let sieve = LinearSieve::new(1000);
+        assert_eq!(sieve.factorize(1), vec![]);
+        assert_eq!(sieve.factorize(84), vec![(2, 2), (3, 1), (7, 1)]);
+        assert_eq!(sieve.factorize(997), vec![(997, 1)]);
+    }
+
+    #[test]
+    fn test_divisors() {
+        let sieve = LinearSieve::new(100);
+        assert_eq!(sieve.divisors(12), vec![1, 2, 3, 4, 6, 12]);
+        assert_eq!(sieve.divisors(1), vec![1]);
+    }
+
+    #[test]
+    fn test_inverses() {
+        type P = promote!(1000000007);
+
+        let inv = inverses::<P>(20);
+        for i in 1..=20u64 {
+            assert_eq!((inv[i as usize] * i).as_u64(), 1);
+        }
+    }
+}